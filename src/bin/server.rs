@@ -1,7 +1,9 @@
-use rudis::{server, DEFAULT_PORT};
+use std::path::PathBuf;
+
+use rudis::config::{Config, ConfigOverrides};
+use rudis::server;
 
 use structopt::StructOpt;
-use tokio::net::TcpListener;
 use tokio::signal;
 
 #[tokio::main]
@@ -11,12 +13,20 @@ pub async fn main() -> rudis::Result<()> {
     tracing_subscriber::fmt::try_init()?;
 
     let cli = Cli::from_args();
-    let port = cli.port.as_deref().unwrap_or(DEFAULT_PORT);
 
-    // Bind a TCP listener
-    let listener = TcpListener::bind(&format!("127.0.0.1:{}", port)).await?;
+    let overrides = ConfigOverrides {
+        host: cli.host,
+        port: cli.port,
+        max_connections: cli.max_connections,
+        snapshot_file: cli.snapshot_file,
+        snapshot_interval: cli.snapshot_interval,
+        idle_timeout: cli.idle_timeout,
+        idle_shutdown: cli.idle_shutdown,
+    };
+
+    let config = Config::load(cli.config.as_deref(), overrides)?;
 
-    server::run(listener, signal::ctrl_c()).await;
+    server::run(config, signal::ctrl_c()).await?;
 
     Ok(())
 }
@@ -24,6 +34,37 @@ pub async fn main() -> rudis::Result<()> {
 #[derive(StructOpt, Debug)]
 #[structopt(name = "mini-redis-server", version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"), about = "A Redis server")]
 struct Cli {
+    /// Path to an optional TOML config file. Environment variables and other
+    /// CLI flags take precedence over values read from it.
+    #[structopt(long = "--config", parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// Address to bind to. Defaults to 127.0.0.1.
+    #[structopt(long = "--host")]
+    host: Option<String>,
+
     #[structopt(name = "port", long = "--port")]
-    port: Option<String>,
-}
\ No newline at end of file
+    port: Option<u16>,
+
+    /// Maximum number of simultaneous client connections.
+    #[structopt(long = "--max-connections")]
+    max_connections: Option<usize>,
+
+    /// Enables periodic snapshot persistence, writing to this file.
+    #[structopt(long = "--snapshot-file")]
+    snapshot_file: Option<String>,
+
+    /// How often, in seconds, to write a snapshot when `--snapshot-file` is set.
+    #[structopt(long = "--snapshot-interval")]
+    snapshot_interval: Option<u64>,
+
+    /// Close a connection after this many seconds of inactivity. Disabled (wait
+    /// indefinitely) by default.
+    #[structopt(long = "--idle-timeout")]
+    idle_timeout: Option<u64>,
+
+    /// Shut the server down after this many seconds with no active
+    /// connections. Disabled (run forever) by default.
+    #[structopt(long = "--idle-shutdown")]
+    idle_shutdown: Option<u64>,
+}
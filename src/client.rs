@@ -3,8 +3,11 @@ use std::io::{Error, ErrorKind};
 use std::time::Duration;
 use bytes::Bytes;
 use tokio::net::{TcpSocket, TcpStream, ToSocketAddrs};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
 use tracing::debug;
-use crate::cmd::{Get, Set};
+use crate::cmd::{Get, Publish, Set, Subscribe, Unsubscribe};
 use crate::{Connection, Frame};
 
 /// Established connection with a Redis server.
@@ -79,6 +82,58 @@ impl Client {
         }
     }
 
+    /// Subscribes the client to the specified `channels`, transitioning it
+    /// into a `Subscriber`. Consumes `self` because a subscribed connection
+    /// may no longer issue `GET`/`SET`-style commands.
+    pub async fn subscribe(mut self, channels: Vec<String>) -> crate::Result<Subscriber> {
+        self.subscribe_cmd(&channels).await?;
+
+        Ok(Subscriber {
+            client: self,
+            subscribed_channels: channels,
+        })
+    }
+
+    /// Writes a SUBSCRIBE frame for `channels` and reads back one
+    /// confirmation per channel, in order.
+    async fn subscribe_cmd(&mut self, channels: &[String]) -> crate::Result<()> {
+        let frame = Subscribe::new(channels.to_vec()).into_frame();
+
+        debug!(request = ?frame);
+
+        self.connection.write_frame(&frame).await?;
+
+        for channel in channels {
+            let response = self.read_response().await?;
+
+            match response {
+                Frame::Array(ref frame) => match frame.as_slice() {
+                    [subscribe, schannel, ..]
+                        if *subscribe == "subscribe" && schannel.to_string() == *channel => {}
+                    _ => return Err(response.to_error()),
+                },
+                frame => return Err(frame.to_error()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Publishes `message` on `channel`, returning the number of
+    /// subscribers that received it.
+    pub async fn publish(&mut self, channel: &str, message: Bytes) -> crate::Result<u64> {
+        let frame = Publish::new(channel, message).into_frame();
+
+        debug!(request = ?frame);
+
+        self.connection.write_frame(&frame).await?;
+
+        match self.read_response().await? {
+            Frame::Integer(response) => Ok(response as u64),
+            frame => Err(frame.to_error()),
+        }
+    }
+
     async fn read_response(&mut self) -> crate::Result<Frame> {
         let response = self.connection.read_frame().await?;
 
@@ -98,4 +153,107 @@ impl Client {
             }
         }
     }
+}
+
+impl Subscriber {
+    /// Returns the set of channels currently subscribed to.
+    pub fn get_subscribed(&self) -> &[String] {
+        &self.subscribed_channels
+    }
+
+    /// Reads the next published message, or `None` if the connection was
+    /// closed by the server.
+    pub async fn next_message(&mut self) -> crate::Result<Option<Message>> {
+        match self.client.connection.read_frame().await? {
+            Some(frame) => {
+                debug!(?frame);
+
+                match frame {
+                    Frame::Array(ref parts) => match parts.as_slice() {
+                        [message, channel, content] if *message == "message" => Ok(Some(Message {
+                            channel: channel.to_string(),
+                            content: Bytes::from(content.to_string()),
+                        })),
+                        _ => Err(frame.to_error()),
+                    },
+                    frame => Err(frame.to_error()),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Converts the subscriber into a stream of messages. The background
+    /// task driving the stream exits once the connection closes or errors.
+    pub fn into_stream(mut self) -> impl Stream<Item = crate::Result<Message>> {
+        let (tx, rx) = mpsc::channel(16);
+
+        tokio::spawn(async move {
+            loop {
+                match self.next_message().await {
+                    Ok(Some(message)) => {
+                        if tx.send(Ok(message)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        let _ = tx.send(Err(err)).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
+    /// Subscribes to additional channels.
+    pub async fn subscribe(&mut self, channels: &[String]) -> crate::Result<()> {
+        self.client.subscribe_cmd(channels).await?;
+
+        self.subscribed_channels
+            .extend(channels.iter().cloned());
+
+        Ok(())
+    }
+
+    /// Unsubscribes from `channels`, or from every subscribed channel if
+    /// `channels` is empty.
+    pub async fn unsubscribe(&mut self, channels: &[String]) -> crate::Result<()> {
+        let frame = Unsubscribe::new(channels).into_frame();
+
+        debug!(request = ?frame);
+
+        self.client.connection.write_frame(&frame).await?;
+
+        let expected = if channels.is_empty() {
+            self.subscribed_channels.len()
+        } else {
+            channels.len()
+        };
+
+        for _ in 0..expected {
+            let response = self.client.read_response().await?;
+
+            match response {
+                Frame::Array(ref frame) => match frame.as_slice() {
+                    [unsubscribe, channel, ..] if *unsubscribe == "unsubscribe" => {
+                        let channel = channel.to_string();
+                        let len = self.subscribed_channels.len();
+
+                        self.subscribed_channels.retain(|c| *c != channel);
+
+                        if self.subscribed_channels.len() != len.saturating_sub(1) {
+                            return Err(format!("could not unsubscribe from `{}`", channel).into());
+                        }
+                    }
+                    _ => return Err(response.to_error()),
+                },
+                frame => return Err(frame.to_error()),
+            }
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
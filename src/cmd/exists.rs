@@ -0,0 +1,71 @@
+use bytes::Bytes;
+use tracing::debug;
+
+use crate::db::Db;
+use crate::parse::{Parse, ParseError};
+use crate::{Connection, Frame};
+
+/// Returns the number of keys that exist among those given. A key is
+/// counted once for each time it is repeated.
+#[derive(Debug)]
+pub struct Exists {
+    keys: Vec<String>,
+}
+
+impl Exists {
+    /// Create a new `Exists` command which checks `keys`.
+    pub fn new(keys: Vec<String>) -> Exists {
+        Exists { keys }
+    }
+
+    /// Parse an `Exists` instance from a received frame.
+    ///
+    /// The `EXISTS` string has already been consumed. At least one key must
+    /// follow.
+    ///
+    /// # Format
+    ///
+    /// ```text
+    /// EXISTS key [key ...]
+    /// ```
+    pub(crate) fn parse_frames(parse: &mut Parse) -> crate::Result<Exists> {
+        use ParseError::EndOfStream;
+
+        let mut keys = vec![parse.next_string()?];
+
+        loop {
+            match parse.next_string() {
+                Ok(key) => keys.push(key),
+                Err(EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Exists { keys })
+    }
+
+    /// Apply the `Exists` command to the specified `Db` instance.
+    ///
+    /// The response is the number of the given keys that are present.
+    pub(crate) async fn apply(self, db: &Db, dst: &mut Connection) -> crate::Result<()> {
+        let count = db.exists(&self.keys);
+
+        let response = Frame::Integer(count as i64);
+
+        debug!(?response);
+
+        dst.write_frame(&response).await?;
+
+        Ok(())
+    }
+
+    /// Converts the command into an equivalent `Frame`.
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from("exists".as_bytes()));
+        for key in self.keys {
+            frame.push_bulk(Bytes::from(key.into_bytes()));
+        }
+        frame
+    }
+}
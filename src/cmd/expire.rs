@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use tracing::debug;
+
+use crate::db::Db;
+use crate::parse::Parse;
+use crate::{Connection, Frame};
+
+/// Sets a timeout on `key`. After the timeout has expired, the key will be
+/// automatically removed.
+#[derive(Debug)]
+pub struct Expire {
+    key: String,
+    seconds: u64,
+}
+
+impl Expire {
+    /// Create a new `Expire` command which expires `key` after `seconds`.
+    pub fn new(key: impl ToString, seconds: u64) -> Expire {
+        Expire {
+            key: key.to_string(),
+            seconds,
+        }
+    }
+
+    /// Parse an `Expire` instance from a received frame.
+    ///
+    /// The `EXPIRE` string has already been consumed.
+    ///
+    /// # Format
+    ///
+    /// ```text
+    /// EXPIRE key seconds
+    /// ```
+    pub(crate) fn parse_frames(parse: &mut Parse) -> crate::Result<Expire> {
+        let key = parse.next_string()?;
+        let seconds = parse.next_int()?;
+
+        Ok(Expire { key, seconds })
+    }
+
+    /// Apply the `Expire` command to the specified `Db` instance.
+    ///
+    /// Replies `1` if the timeout was set, `0` if `key` does not exist.
+    pub(crate) async fn apply(self, db: &Db, dst: &mut Connection) -> crate::Result<()> {
+        let was_set = db.expire(&self.key, Duration::from_secs(self.seconds));
+
+        let response = Frame::Integer(if was_set { 1 } else { 0 });
+
+        debug!(?response);
+
+        dst.write_frame(&response).await?;
+
+        Ok(())
+    }
+
+    /// Converts the command into an equivalent `Frame`.
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from("expire".as_bytes()));
+        frame.push_bulk(Bytes::from(self.key.into_bytes()));
+        frame.push_int(self.seconds);
+        frame
+    }
+}
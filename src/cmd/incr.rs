@@ -0,0 +1,62 @@
+use bytes::Bytes;
+use tracing::debug;
+
+use crate::db::Db;
+use crate::parse::Parse;
+use crate::{Connection, Frame};
+
+/// Increments the integer value stored at `key` by one. If `key` does not
+/// exist, it is set to `0` before performing the operation.
+#[derive(Debug)]
+pub struct Incr {
+    key: String,
+}
+
+impl Incr {
+    /// Create a new `Incr` command which increments `key`.
+    pub fn new(key: impl ToString) -> Incr {
+        Incr {
+            key: key.to_string(),
+        }
+    }
+
+    /// Parse an `Incr` instance from a received frame.
+    ///
+    /// The `INCR` string has already been consumed.
+    ///
+    /// # Format
+    ///
+    /// ```text
+    /// INCR key
+    /// ```
+    pub(crate) fn parse_frames(parse: &mut Parse) -> crate::Result<Incr> {
+        let key = parse.next_string()?;
+
+        Ok(Incr { key })
+    }
+
+    /// Apply the `Incr` command to the specified `Db` instance.
+    ///
+    /// Replies with the value after incrementing, or an error if the
+    /// existing value is not a valid integer.
+    pub(crate) async fn apply(self, db: &Db, dst: &mut Connection) -> crate::Result<()> {
+        let response = match db.incr(&self.key) {
+            Ok(value) => Frame::Integer(value),
+            Err(err) => Frame::Error(err.to_string()),
+        };
+
+        debug!(?response);
+
+        dst.write_frame(&response).await?;
+
+        Ok(())
+    }
+
+    /// Converts the command into an equivalent `Frame`.
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from("incr".as_bytes()));
+        frame.push_bulk(Bytes::from(self.key.into_bytes()));
+        frame
+    }
+}
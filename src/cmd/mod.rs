@@ -1,6 +1,18 @@
+mod del;
+pub use del::Del;
+
+mod exists;
+pub use exists::Exists;
+
+mod expire;
+pub use expire::Expire;
+
 mod get;
 pub use get::Get;
 
+mod incr;
+pub use incr::Incr;
+
 mod publish;
 pub use publish::Publish;
 
@@ -10,6 +22,9 @@ pub use set::Set;
 mod subscribe;
 pub use subscribe::{Subscribe, Unsubscribe};
 
+mod ttl;
+pub use ttl::Ttl;
+
 mod unknown;
 pub use unknown::Unknown;
 use crate::db::Db;
@@ -24,10 +39,15 @@ use crate::shutdown::Shutdown;
 /// Methods called on `Command` are delegated to the command implementation.
 #[derive(Debug)]
 pub enum Command {
+    Del(Del),
+    Exists(Exists),
+    Expire(Expire),
     Get(Get),
+    Incr(Incr),
     Publish(Publish),
     Set(Set),
     Subscribe(Subscribe),
+    Ttl(Ttl),
     Unsubscribe(Unsubscribe),
     Unknown(Unknown),
 }
@@ -38,8 +58,16 @@ impl Command {
         let cmd_name = parse.next_string()?;
 
         let command = match &cmd_name[..] {
+            "del" => Command::Del(Del::parse_frames(&mut parse)?),
+            "exists" => Command::Exists(Exists::parse_frames(&mut parse)?),
+            "expire" => Command::Expire(Expire::parse_frames(&mut parse)?),
             "get" => Command::Get(Get::parse_frames(&mut parse)?),
+            "incr" => Command::Incr(Incr::parse_frames(&mut parse)?),
             "set" => Command::Set(Set::parse_frames(&mut parse)?),
+            "subscribe" => Command::Subscribe(Subscribe::parse_frames(&mut parse)?),
+            "ttl" => Command::Ttl(Ttl::parse_frames(&mut parse)?),
+            "unsubscribe" => Command::Unsubscribe(Unsubscribe::parse_frames(&mut parse)?),
+            "publish" => Command::Publish(Publish::parse_frames(&mut parse)?),
             _ => {
                 return Ok(Command::Unknown(Unknown::new(cmd_name)));
             }
@@ -59,23 +87,33 @@ impl Command {
         use Command::*;
 
         match self {
+            Del(cmd) => cmd.apply(db, dst).await,
+            Exists(cmd) => cmd.apply(db, dst).await,
+            Expire(cmd) => cmd.apply(db, dst).await,
             Get(cmd) => cmd.apply(db, dst).await,
+            Incr(cmd) => cmd.apply(db, dst).await,
             Set(cmd) => cmd.apply(db, dst).await,
+            Subscribe(cmd) => cmd.apply(db, dst, shutdown).await,
+            Ttl(cmd) => cmd.apply(db, dst).await,
+            Publish(cmd) => cmd.apply(db, dst).await,
             Unknown(cmd) => cmd.apply(dst).await,
-            // Subscribe(cmd) => cmd.apply(db, dst, shutdown).await,
-            // Publish(cmd) => cmd.apply(db, dst).await,
             // `Unsubscribe` cannot be applied. It may only be received from the
             // context of a `Subscribe` command.
-            _  => Err("Unimplemented command.".into()),
+            Unsubscribe(_) => Err("Unsubscribe is unimplemented outside of a subscribe context.".into()),
         }
     }
 
     pub(crate) fn get_name(&self) -> &str {
         match self {
+            Command::Del(_) => "del",
+            Command::Exists(_) => "exists",
+            Command::Expire(_) => "expire",
             Command::Get(_) => "get",
+            Command::Incr(_) => "incr",
             Command::Publish(_) => "pub",
             Command::Set(_) => "set",
             Command::Subscribe(_) => "subscribe",
+            Command::Ttl(_) => "ttl",
             Command::Unsubscribe(_) => "unsubscribe",
             Command::Unknown(cmd) => cmd.get_name(),
         }
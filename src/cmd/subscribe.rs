@@ -1,4 +1,17 @@
+use std::pin::Pin;
 
+use bytes::Bytes;
+use tokio::select;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt, StreamMap};
+use tracing::debug;
+
+use crate::cmd::{Command, Unknown};
+use crate::db::Db;
+use crate::parse::{Parse, ParseError};
+use crate::shutdown::Shutdown;
+use crate::{Connection, Frame};
 
 /// Subscribes the client to one or more channels.
 ///
@@ -17,4 +30,233 @@ pub struct Subscribe {
 #[derive(Clone, Debug)]
 pub struct Unsubscribe {
     channels: Vec<String>,
-}
\ No newline at end of file
+}
+
+/// Stream of messages. The stream receives messages from the
+/// `broadcast::Receiver`, including any `Lagged` errors, which
+/// `Subscribe::apply` skips over.
+type Messages = Pin<Box<dyn Stream<Item = Result<Bytes, BroadcastStreamRecvError>> + Send>>;
+
+impl Subscribe {
+    /// Creates a new `Subscribe` command to listen on the specified channels.
+    pub fn new(channels: Vec<String>) -> Subscribe {
+        Subscribe { channels }
+    }
+
+    /// Parse a `Subscribe` instance from a received frame.
+    ///
+    /// The `SUBSCRIBE` string has already been consumed. At least one channel
+    /// name must follow.
+    ///
+    /// # Format
+    ///
+    /// ```text
+    /// SUBSCRIBE channel [channel ...]
+    /// ```
+    pub(crate) fn parse_frames(parse: &mut Parse) -> crate::Result<Subscribe> {
+        use ParseError::EndOfStream;
+
+        let mut channels = vec![parse.next_string()?];
+
+        loop {
+            match parse.next_string() {
+                Ok(s) => channels.push(s),
+                Err(EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Subscribe { channels })
+    }
+
+    /// Apply the `Subscribe` command to the specified `Db` instance.
+    ///
+    /// This function is the entry point and includes the initial list of
+    /// channels to subscribe to. Additional `subscribe` and `unsubscribe`
+    /// commands may be received from the client and the list of subscriptions
+    /// are updated accordingly.
+    ///
+    /// [here]: https://redis.io/topics/pubsub
+    pub(crate) async fn apply(
+        mut self,
+        db: &Db,
+        dst: &mut Connection,
+        shutdown: &mut Shutdown,
+    ) -> crate::Result<()> {
+        // Each individual channel subscription is handled using a
+        // `BroadcastStream`. Messages are then able to be received from many
+        // channels at the same time via `StreamMap`.
+        let mut subscriptions = StreamMap::new();
+
+        loop {
+            // `self.channels` is used to track additional channels to
+            // subscribe to. New subscriptions may be added as the loop runs.
+            for channel_name in self.channels.drain(..) {
+                subscribe_to_channel(channel_name, &mut subscriptions, db, dst).await?;
+            }
+
+            select! {
+                // Receive messages from subscribed channels.
+                Some((channel_name, msg)) = subscriptions.next() => {
+                    match msg {
+                        Ok(msg) => dst.write_frame(&make_message_frame(channel_name, msg)).await?,
+                        // A lagging receiver just means messages were
+                        // dropped; carry on rather than erroring out.
+                        Err(BroadcastStreamRecvError::Lagged(_)) => {}
+                    }
+                }
+                // A new frame has been received from the connection. Only
+                // (UN)SUBSCRIBE commands are permitted in this state.
+                res = dst.read_frame() => {
+                    let frame = match res? {
+                        Some(frame) => frame,
+                        // The connection has been closed.
+                        None => return Ok(()),
+                    };
+
+                    handle_command(frame, &mut self.channels, &mut subscriptions, dst).await?;
+                }
+                _ = shutdown.recv() => {
+                    return Ok(());
+                }
+            };
+        }
+    }
+
+    /// Converts the command into an equivalent `Frame`.
+    ///
+    /// This is called by the client when encoding a `Subscribe` command to
+    /// send to the server.
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from("subscribe".as_bytes()));
+        for channel in self.channels {
+            frame.push_bulk(Bytes::from(channel.into_bytes()));
+        }
+        frame
+    }
+}
+
+/// Subscribe to a single channel, registering it in `subscriptions` and
+/// notifying the client with the standard subscribe confirmation frame.
+async fn subscribe_to_channel(
+    channel_name: String,
+    subscriptions: &mut StreamMap<String, Messages>,
+    db: &Db,
+    dst: &mut Connection,
+) -> crate::Result<()> {
+    let rx = db.subscribe(channel_name.clone());
+
+    let rx = Box::pin(BroadcastStream::new(rx));
+
+    subscriptions.insert(channel_name.clone(), rx);
+
+    let response = make_subscribe_frame(channel_name, subscriptions.len());
+    dst.write_frame(&response).await?;
+
+    Ok(())
+}
+
+/// Handle a command received while in the subscribed state. Only additional
+/// SUBSCRIBE and UNSUBSCRIBE commands are valid; anything else results in an
+/// "unknown command" response.
+async fn handle_command(
+    frame: Frame,
+    subscribe_to: &mut Vec<String>,
+    subscriptions: &mut StreamMap<String, Messages>,
+    dst: &mut Connection,
+) -> crate::Result<()> {
+    match Command::from_frame(frame)? {
+        Command::Subscribe(subscribe) => {
+            subscribe_to.extend(subscribe.channels);
+        }
+        Command::Unsubscribe(mut unsubscribe) => {
+            if unsubscribe.channels.is_empty() {
+                unsubscribe.channels = subscriptions.keys().map(|k| k.to_string()).collect();
+            }
+
+            for channel_name in unsubscribe.channels {
+                subscriptions.remove(&channel_name);
+
+                let response = make_unsubscribe_frame(channel_name, subscriptions.len());
+                dst.write_frame(&response).await?;
+            }
+        }
+        command => {
+            let cmd = Unknown::new(command.get_name());
+            cmd.apply(dst).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn make_subscribe_frame(channel_name: String, num_subs: usize) -> Frame {
+    let mut response = Frame::array();
+    response.push_bulk(Bytes::from_static(b"subscribe"));
+    response.push_bulk(Bytes::from(channel_name.into_bytes()));
+    response.push_int(num_subs as u64);
+    response
+}
+
+fn make_unsubscribe_frame(channel_name: String, num_subs: usize) -> Frame {
+    let mut response = Frame::array();
+    response.push_bulk(Bytes::from_static(b"unsubscribe"));
+    response.push_bulk(Bytes::from(channel_name.into_bytes()));
+    response.push_int(num_subs as u64);
+    response
+}
+
+fn make_message_frame(channel_name: String, msg: Bytes) -> Frame {
+    let mut response = Frame::array();
+    response.push_bulk(Bytes::from_static(b"message"));
+    response.push_bulk(Bytes::from(channel_name.into_bytes()));
+    response.push_bulk(msg);
+    response
+}
+
+impl Unsubscribe {
+    /// Creates a new `Unsubscribe` command for the given channels.
+    pub fn new(channels: &[String]) -> Unsubscribe {
+        Unsubscribe {
+            channels: channels.to_vec(),
+        }
+    }
+
+    /// Parse a `Unsubscribe` instance from a received frame.
+    ///
+    /// The `UNSUBSCRIBE` string has already been consumed. Zero or more
+    /// channel names may follow; if none are given the subscriber is
+    /// unsubscribed from every channel it is currently on.
+    ///
+    /// # Format
+    ///
+    /// ```text
+    /// UNSUBSCRIBE [channel [channel ...]]
+    /// ```
+    pub(crate) fn parse_frames(parse: &mut Parse) -> crate::Result<Unsubscribe> {
+        use ParseError::EndOfStream;
+
+        let mut channels = vec![];
+
+        loop {
+            match parse.next_string() {
+                Ok(s) => channels.push(s),
+                Err(EndOfStream) => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Unsubscribe { channels })
+    }
+
+    /// Converts the command into an equivalent `Frame`.
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from("unsubscribe".as_bytes()));
+        for channel in self.channels {
+            frame.push_bulk(Bytes::from(channel.into_bytes()));
+        }
+        frame
+    }
+}
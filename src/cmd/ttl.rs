@@ -0,0 +1,60 @@
+use bytes::Bytes;
+use tracing::debug;
+
+use crate::db::Db;
+use crate::parse::Parse;
+use crate::{Connection, Frame};
+
+/// Returns the remaining time to live of `key`, in seconds.
+///
+/// Replies `-2` if `key` does not exist, `-1` if `key` exists but has no
+/// associated expiration, otherwise the remaining seconds.
+#[derive(Debug)]
+pub struct Ttl {
+    key: String,
+}
+
+impl Ttl {
+    /// Create a new `Ttl` command which checks `key`.
+    pub fn new(key: impl ToString) -> Ttl {
+        Ttl {
+            key: key.to_string(),
+        }
+    }
+
+    /// Parse a `Ttl` instance from a received frame.
+    ///
+    /// The `TTL` string has already been consumed.
+    ///
+    /// # Format
+    ///
+    /// ```text
+    /// TTL key
+    /// ```
+    pub(crate) fn parse_frames(parse: &mut Parse) -> crate::Result<Ttl> {
+        let key = parse.next_string()?;
+
+        Ok(Ttl { key })
+    }
+
+    /// Apply the `Ttl` command to the specified `Db` instance.
+    pub(crate) async fn apply(self, db: &Db, dst: &mut Connection) -> crate::Result<()> {
+        let ttl = db.ttl(&self.key);
+
+        let response = Frame::Integer(ttl);
+
+        debug!(?response);
+
+        dst.write_frame(&response).await?;
+
+        Ok(())
+    }
+
+    /// Converts the command into an equivalent `Frame`.
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from("ttl".as_bytes()));
+        frame.push_bulk(Bytes::from(self.key.into_bytes()));
+        frame
+    }
+}
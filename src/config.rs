@@ -0,0 +1,152 @@
+use std::env;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::persistence::Persistence;
+
+/// Default bind address, used when nothing overrides it.
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// Default maximum number of simultaneous connections.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 256;
+
+/// Fully resolved server configuration.
+///
+/// Built by [`Config::load`], which layers, from lowest to highest priority:
+/// built-in defaults, an optional config file, environment variables, then
+/// explicit CLI overrides.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub max_connections: usize,
+    pub persistence: Option<Persistence>,
+    pub idle_timeout: Option<Duration>,
+    pub idle_shutdown: Option<Duration>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            host: DEFAULT_HOST.to_string(),
+            port: crate::DEFAULT_PORT.parse().expect("DEFAULT_PORT must be a valid port"),
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            persistence: None,
+            idle_timeout: None,
+            idle_shutdown: None,
+        }
+    }
+}
+
+/// Overrides coming from the CLI, the highest-priority layer passed to
+/// [`Config::load`].
+#[derive(Debug, Default)]
+pub struct ConfigOverrides {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub max_connections: Option<usize>,
+    pub snapshot_file: Option<String>,
+    pub snapshot_interval: Option<u64>,
+    pub idle_timeout: Option<u64>,
+    pub idle_shutdown: Option<u64>,
+}
+
+/// On-disk representation of the optional TOML config file. Every field is
+/// optional so a file only needs to specify what it wants to override.
+#[derive(Debug, Default, Deserialize)]
+struct FileConfig {
+    host: Option<String>,
+    port: Option<u16>,
+    max_connections: Option<usize>,
+    snapshot_file: Option<String>,
+    snapshot_interval: Option<u64>,
+    idle_timeout: Option<u64>,
+    idle_shutdown: Option<u64>,
+}
+
+impl Config {
+    /// Builds a `Config` by layering built-in defaults, `config_path` (if
+    /// given), environment variables, and finally `overrides`.
+    pub fn load(config_path: Option<&Path>, overrides: ConfigOverrides) -> crate::Result<Config> {
+        let mut config = Config::default();
+
+        if let Some(path) = config_path {
+            let data = std::fs::read_to_string(path)?;
+            let file_config: FileConfig = toml::from_str(&data)?;
+            apply_snapshot(&mut config, file_config.snapshot_file, file_config.snapshot_interval);
+
+            if let Some(host) = file_config.host {
+                config.host = host;
+            }
+            if let Some(port) = file_config.port {
+                config.port = port;
+            }
+            if let Some(max_connections) = file_config.max_connections {
+                config.max_connections = max_connections;
+            }
+            if let Some(secs) = file_config.idle_timeout {
+                config.idle_timeout = Some(Duration::from_secs(secs));
+            }
+            if let Some(secs) = file_config.idle_shutdown {
+                config.idle_shutdown = Some(Duration::from_secs(secs));
+            }
+        }
+
+        if let Ok(host) = env::var("RUDIS_HOST") {
+            config.host = host;
+        }
+        if let Ok(port) = env::var("RUDIS_PORT") {
+            config.port = port.parse()?;
+        }
+        if let Ok(max_connections) = env::var("RUDIS_MAX_CONNECTIONS") {
+            config.max_connections = max_connections.parse()?;
+        }
+        let env_snapshot_interval = env::var("RUDIS_SNAPSHOT_INTERVAL").ok().and_then(|s| s.parse().ok());
+        apply_snapshot(&mut config, env::var("RUDIS_SNAPSHOT_FILE").ok(), env_snapshot_interval);
+        if let Ok(secs) = env::var("RUDIS_IDLE_TIMEOUT") {
+            config.idle_timeout = Some(Duration::from_secs(secs.parse()?));
+        }
+        if let Ok(secs) = env::var("RUDIS_IDLE_SHUTDOWN") {
+            config.idle_shutdown = Some(Duration::from_secs(secs.parse()?));
+        }
+
+        if let Some(host) = overrides.host {
+            config.host = host;
+        }
+        if let Some(port) = overrides.port {
+            config.port = port;
+        }
+        if let Some(max_connections) = overrides.max_connections {
+            config.max_connections = max_connections;
+        }
+        apply_snapshot(&mut config, overrides.snapshot_file, overrides.snapshot_interval);
+        if let Some(secs) = overrides.idle_timeout {
+            config.idle_timeout = Some(Duration::from_secs(secs));
+        }
+        if let Some(secs) = overrides.idle_shutdown {
+            config.idle_shutdown = Some(Duration::from_secs(secs));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Applies a `snapshot_file`/`snapshot_interval` pair to `config`, defaulting
+/// the interval to 60 seconds when a file is given without one. A layer that
+/// sets only `snapshot_interval` still retunes the save cadence of a
+/// `snapshot_file` set by a lower-priority layer.
+fn apply_snapshot(config: &mut Config, snapshot_file: Option<String>, snapshot_interval: Option<u64>) {
+    match (snapshot_file, snapshot_interval) {
+        (Some(path), interval) => {
+            config.persistence = Some(Persistence::new(path, Duration::from_secs(interval.unwrap_or(60))));
+        }
+        (None, Some(secs)) => {
+            if let Some(persistence) = &mut config.persistence {
+                persistence.interval = Duration::from_secs(secs);
+            }
+        }
+        (None, None) => {}
+    }
+}
@@ -1,21 +1,29 @@
 use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration};
 use bytes::Bytes;
 use tokio::time::Instant;
 use tokio::sync::{broadcast, Notify};
 use tokio::time;
-use log::debug;
+use tracing::{debug, warn};
 use tokio_stream::StreamExt;
 
+use crate::persistence::{self, Persistence};
 use crate::Result;
 
+/// Maximum number of messages buffered per pub/sub channel before a
+/// subscriber that falls behind starts missing them.
+const CAP: usize = 1024;
+
 /// A wrapper around a `Db` instance. This exists to allow orderly cleanup
 /// of the `Db` by signalling the background purge task to shut down when
 /// this struct is dropped.
 #[derive(Debug)]
 pub(crate) struct DbDropGuard {
     db: Db,
+
+    persistence: Option<Persistence>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,9 +61,10 @@ struct Entry {
 }
 
 impl DbDropGuard {
-    pub(crate) fn new() ->  DbDropGuard {
+    pub(crate) fn new(persistence: Option<Persistence>) ->  DbDropGuard {
         DbDropGuard {
-            db : Db::new()
+            db: Db::new(persistence.clone()),
+            persistence,
         }
     }
 
@@ -68,11 +77,19 @@ impl Drop for DbDropGuard {
     fn drop(&mut self) {
         // Signal the 'Db' instance to shut down the task that purges expired keys
         self.db.shutdown_purge_task();
+
+        // Persist a final snapshot synchronously so a graceful shutdown never
+        // loses data written since the last background save.
+        if let Some(persistence) = &self.persistence {
+            if let Err(err) = self.db.save_snapshot(&persistence.path) {
+                warn!(%err, "failed to save snapshot on shutdown");
+            }
+        }
     }
 }
 
 impl Db {
-    pub(crate) fn new() -> Db {
+    pub(crate) fn new(persistence: Option<Persistence>) -> Db {
         let shared = Arc::new(Shared{
             state: Mutex::new(State{
                 entries: HashMap::new(),
@@ -84,9 +101,23 @@ impl Db {
             background_task: Notify::new(),
         });
 
-        tokio::spawn(purge_expired_tasks(shared.clone()));
+        let db = Db { shared };
+
+        if let Some(persistence) = &persistence {
+            if persistence.path.exists() {
+                if let Err(err) = db.load_snapshot(&persistence.path) {
+                    warn!(%err, "failed to load snapshot");
+                }
+            }
+        }
+
+        tokio::spawn(purge_expired_tasks(db.shared.clone()));
+
+        if let Some(persistence) = persistence {
+            tokio::spawn(snapshot_tasks(db.clone(), persistence));
+        }
 
-        Db { shared }
+        db
     }
 
     pub(crate) fn get(&self, key: &str) -> Option<Bytes> {
@@ -144,6 +175,181 @@ impl Db {
         }
     }
 
+    /// Returns a `Receiver` for the requested channel.
+    ///
+    /// The returned `Receiver` is used to receive values broadcast by `PUBLISH`
+    /// commands.
+    pub(crate) fn subscribe(&self, channel: String) -> broadcast::Receiver<Bytes> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        state
+            .pub_sub
+            .entry(channel)
+            .or_insert_with(|| broadcast::channel(CAP).0)
+            .subscribe()
+    }
+
+    /// Publish a message to the channel. Returns the number of subscribers
+    /// listening on the channel.
+    pub(crate) fn publish(&self, channel: &str, value: Bytes) -> usize {
+        let state = self.shared.state.lock().unwrap();
+
+        state
+            .pub_sub
+            .get(channel)
+            // On a successful message send on the broadcast channel, the
+            // number of subscribers is returned. An error indicates there are
+            // no receivers, in which case, `0` should be returned.
+            .map(|tx| tx.send(value).unwrap_or(0))
+            .unwrap_or(0)
+    }
+
+    /// Removes `keys`, along with any expiration tracking for them. Returns
+    /// the number of keys that were actually present and removed.
+    pub(crate) fn del(&self, keys: &[String]) -> usize {
+        let mut state = self.shared.state.lock().unwrap();
+        let mut removed = 0;
+
+        for key in keys {
+            if let Some(entry) = state.entries.remove(key) {
+                if let Some(when) = entry.expired_at {
+                    state.expirations.remove(&(when, entry.id));
+                }
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    /// Returns how many of `keys` are currently present.
+    pub(crate) fn exists(&self, keys: &[String]) -> usize {
+        let state = self.shared.state.lock().unwrap();
+
+        keys.iter()
+            .filter(|key| state.entries.contains_key(*key))
+            .count()
+    }
+
+    /// Sets (or replaces) the expiration of `key` to `duration` from now.
+    /// Returns `false` if `key` does not exist.
+    pub(crate) fn expire(&self, key: &str, duration: Duration) -> bool {
+        let mut state = self.shared.state.lock().unwrap();
+
+        let id = match state.entries.get(key) {
+            Some(entry) => entry.id,
+            None => return false,
+        };
+
+        let when = Instant::now() + duration;
+
+        // Only notify the worker task if the newly inserted expiration is the
+        // **next** key to evict, same as `Db::set`.
+        let notify = state
+            .next_expiration()
+            .map(|expiration| expiration > when)
+            .unwrap_or(true);
+
+        if let Some(prev_when) = state.entries.get(key).and_then(|entry| entry.expired_at) {
+            state.expirations.remove(&(prev_when, id));
+        }
+
+        state.expirations.insert((when, id), key.to_string());
+        state.entries.get_mut(key).unwrap().expired_at = Some(when);
+
+        drop(state);
+
+        if notify {
+            self.shared.background_task.notify_one();
+        }
+
+        true
+    }
+
+    /// Returns the remaining time to live of `key`, in seconds: `-2` if
+    /// `key` does not exist, `-1` if it exists but has no expiration.
+    pub(crate) fn ttl(&self, key: &str) -> i64 {
+        let state = self.shared.state.lock().unwrap();
+
+        match state.entries.get(key) {
+            None => -2,
+            Some(entry) => match entry.expired_at {
+                None => -1,
+                Some(when) => {
+                    let now = Instant::now();
+                    if when <= now {
+                        -2
+                    } else {
+                        (when - now).as_secs() as i64
+                    }
+                }
+            },
+        }
+    }
+
+    /// Increments the integer value stored at `key` by one, treating a
+    /// missing key as `0`. Returns an error if the existing value is not a
+    /// valid integer, or if incrementing it would overflow `i64`.
+    pub(crate) fn incr(&self, key: &str) -> Result<i64> {
+        let current = match self.get(key) {
+            Some(value) => std::str::from_utf8(&value)
+                .ok()
+                .and_then(|s| s.parse::<i64>().ok())
+                .ok_or_else(|| format!("value at key `{}` is not an integer", key))?,
+            None => 0,
+        };
+
+        let value = current
+            .checked_add(1)
+            .ok_or_else(|| format!("increment of key `{}` would overflow", key))?;
+
+        // Preserve any existing expiration instead of letting `set` clear it.
+        let expire = self.remaining_ttl(key);
+        self.set(key.to_string(), Bytes::from(value.to_string()), expire);
+
+        Ok(value)
+    }
+
+    /// Returns the duration remaining until `key` expires, or `None` if it
+    /// has no expiration (or does not exist).
+    fn remaining_ttl(&self, key: &str) -> Option<Duration> {
+        let state = self.shared.state.lock().unwrap();
+        let when = state.entries.get(key)?.expired_at?;
+        Some(when.saturating_duration_since(Instant::now()))
+    }
+
+    /// Loads a previously saved snapshot from `path`, repopulating `entries`
+    /// via the existing `set` logic so expirations are tracked normally.
+    fn load_snapshot(&self, path: &Path) -> Result<()> {
+        for (key, value, ttl) in persistence::load(path)? {
+            self.set(key, value, ttl);
+        }
+
+        Ok(())
+    }
+
+    /// Writes every live, non-expired entry to `path`, recording each key's
+    /// remaining TTL rather than its absolute expiration instant.
+    fn save_snapshot(&self, path: &Path) -> Result<()> {
+        let state = self.shared.state.lock().unwrap();
+
+        let now = Instant::now();
+
+        let entries: Vec<_> = state
+            .entries
+            .iter()
+            .filter_map(|(key, entry)| match entry.expired_at {
+                Some(when) if when <= now => None,
+                Some(when) => Some((key.clone(), entry.data.clone(), Some(when - now))),
+                None => Some((key.clone(), entry.data.clone(), None)),
+            })
+            .collect();
+
+        drop(state);
+
+        persistence::save(path, entries)
+    }
+
     /// Signals the purge background task to shut down. This is called by the
     /// `DbShutdown`s `Drop` implementation.
     fn shutdown_purge_task(&self) {
@@ -195,6 +401,27 @@ async fn purge_expired_tasks(shared: Arc<Shared>) {
     debug!("Purge background task shut down")
 }
 
+/// Background task that periodically writes a snapshot of `db` to disk,
+/// modeled on a standard background-save (RDB) worker. Runs alongside
+/// `purge_expired_tasks` for the lifetime of the `Db`.
+async fn snapshot_tasks(db: Db, persistence: Persistence) {
+    let mut interval = time::interval(persistence.interval);
+
+    loop {
+        interval.tick().await;
+
+        if db.shared.is_shutdown() {
+            break;
+        }
+
+        if let Err(err) = db.save_snapshot(&persistence.path) {
+            warn!(%err, "failed to save snapshot");
+        }
+    }
+
+    debug!("Snapshot background task shut down")
+}
+
 impl Shared {
     fn purge_expired_keys(&self) -> Option<Instant> {
         let mut state = self.state.lock().unwrap();
@@ -223,4 +450,77 @@ impl Shared {
     fn is_shutdown(&self) -> bool {
         self.state.lock().unwrap().shutdown
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn incr_treats_missing_key_as_zero() {
+        let db = Db::new(None);
+
+        assert_eq!(db.incr("counter").unwrap(), 1);
+        assert_eq!(db.incr("counter").unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn incr_rejects_non_integer_value() {
+        let db = Db::new(None);
+        db.set("key".to_string(), Bytes::from("not a number"), None);
+
+        assert!(db.incr("key").is_err());
+    }
+
+    #[tokio::test]
+    async fn incr_rejects_overflow() {
+        let db = Db::new(None);
+        db.set("key".to_string(), Bytes::from(i64::MAX.to_string()), None);
+
+        assert!(db.incr("key").is_err());
+    }
+
+    #[tokio::test]
+    async fn incr_preserves_existing_ttl() {
+        let db = Db::new(None);
+        db.set("key".to_string(), Bytes::from("41"), Some(Duration::from_secs(60)));
+
+        db.incr("key").unwrap();
+
+        let ttl = db.ttl("key");
+        assert!(ttl > 0 && ttl <= 60);
+    }
+
+    #[tokio::test]
+    async fn ttl_reports_missing_and_no_expiration() {
+        let db = Db::new(None);
+
+        assert_eq!(db.ttl("missing"), -2);
+
+        db.set("key".to_string(), Bytes::from("value"), None);
+        assert_eq!(db.ttl("key"), -1);
+    }
+
+    #[tokio::test]
+    async fn save_and_load_snapshot_round_trip_skips_expired_entries() {
+        let path = std::env::temp_dir().join("rudis-db-snapshot-round-trip.json");
+
+        let db = Db::new(None);
+        db.set("alive".to_string(), Bytes::from("value"), None);
+        db.set("about-to-expire".to_string(), Bytes::from("gone"), Some(Duration::from_secs(0)));
+
+        // Give the already-expired entry time to pass its deadline without
+        // relying on the background purge task to have removed it yet.
+        time::sleep(Duration::from_millis(10)).await;
+
+        db.save_snapshot(&path).unwrap();
+
+        let loaded = Db::new(None);
+        loaded.load_snapshot(&path).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get("alive"), Some(Bytes::from("value")));
+        assert_eq!(loaded.get("about-to-expire"), None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the background RDB-style snapshot worker.
+///
+/// When present, `Db::new` loads `path` on startup (if it exists) and spawns
+/// a background task that re-writes it every `interval`.
+#[derive(Debug, Clone)]
+pub struct Persistence {
+    /// Path of the snapshot file on disk.
+    pub path: PathBuf,
+
+    /// How often the background task writes a fresh snapshot.
+    pub interval: Duration,
+}
+
+impl Persistence {
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Persistence {
+        Persistence {
+            path: path.into(),
+            interval,
+        }
+    }
+}
+
+/// A single key recorded in a snapshot, along with the remaining time until
+/// expiration, if any.
+///
+/// `Duration` is stored rather than `tokio::time::Instant` because an
+/// `Instant` is tied to this process's clock and cannot be serialized.
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotEntry {
+    key: String,
+    value: Vec<u8>,
+    ttl: Option<Duration>,
+}
+
+/// Serializes `entries` and atomically replaces the file at `path` with the
+/// result, so that a crash mid-write never corrupts a previous snapshot.
+pub(crate) fn save(path: &Path, entries: Vec<(String, Bytes, Option<Duration>)>) -> crate::Result<()> {
+    let snapshot: Vec<SnapshotEntry> = entries
+        .into_iter()
+        .map(|(key, value, ttl)| SnapshotEntry {
+            key,
+            value: value.to_vec(),
+            ttl,
+        })
+        .collect();
+
+    let tmp_path = path.with_extension("tmp");
+    let data = serde_json::to_vec(&snapshot)?;
+
+    std::fs::write(&tmp_path, data)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+/// Reads and deserializes the snapshot at `path`.
+pub(crate) fn load(path: &Path) -> crate::Result<Vec<(String, Bytes, Option<Duration>)>> {
+    let data = std::fs::read(path)?;
+    let snapshot: Vec<SnapshotEntry> = serde_json::from_slice(&data)?;
+
+    Ok(snapshot
+        .into_iter()
+        .map(|entry| (entry.key, Bytes::from(entry.value), entry.ttl))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_load_round_trip_preserves_ttl() {
+        let path = std::env::temp_dir().join("rudis-persistence-round-trip.json");
+
+        let entries = vec![
+            ("no-ttl".to_string(), Bytes::from("forever"), None),
+            (
+                "with-ttl".to_string(),
+                Bytes::from("expires"),
+                Some(Duration::from_secs(30)),
+            ),
+        ];
+
+        save(&path, entries.clone()).unwrap();
+        let loaded = load(&path).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, entries);
+    }
+}
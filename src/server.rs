@@ -1,11 +1,13 @@
 use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::{broadcast, mpsc, Semaphore};
+use tokio::sync::{broadcast, mpsc, Notify, Semaphore};
 use tokio::time;
 use tracing::{debug, info, error, instrument};
 use crate::{Command, Connection, Frame};
+use crate::config::Config;
 use crate::db::{Db, DbDropGuard};
 use crate::shutdown::Shutdown;
 
@@ -20,7 +22,22 @@ struct Listener {
     notify_shutdown: broadcast::Sender<()>,
 
     shutdown_complete_rx: mpsc::Receiver<()>,
-    shutdown_complete_tx: mpsc::Sender<()>, 
+    shutdown_complete_tx: mpsc::Sender<()>,
+
+    /// Number of connections currently being handled. Used to drive the
+    /// optional "shut down once idle" behavior.
+    active_connections: Arc<AtomicUsize>,
+
+    /// Notified every time a new connection is accepted, so a pending idle
+    /// shutdown countdown can be cancelled.
+    connection_added: Arc<Notify>,
+
+    /// Per-connection idle read timeout. `None` disables it (wait indefinitely).
+    idle_timeout: Option<Duration>,
+
+    /// How long to wait with zero active connections before shutting the
+    /// server down. `None` disables it (run forever).
+    idle_shutdown: Option<Duration>,
 }
 
 struct Handler {
@@ -33,22 +50,41 @@ struct Handler {
     shutdown: Shutdown,
 
     _shutdown_complete: mpsc::Sender<()>,
+
+    active_connections: Arc<AtomicUsize>,
+
+    connection_added: Arc<Notify>,
+
+    notify_shutdown: broadcast::Sender<()>,
+
+    idle_timeout: Option<Duration>,
+
+    idle_shutdown: Option<Duration>,
 }
 
-const MAX_CONNECTIONS: usize = 256;
+pub async fn run(config: Config, shutdown: impl Future) -> crate::Result<()> {
+    let listener = TcpListener::bind((config.host.as_str(), config.port)).await?;
 
-pub async fn run(listener: TcpListener, shutdown: impl Future) {
     let (notify_shutdown, _) = broadcast::channel(1);
 
+    // Also subscribed here so the idle-shutdown countdown (which only has
+    // `Handler`s as subscribers otherwise) can actually stop the accept loop
+    // once every connection has dropped.
+    let mut idle_shutdown_rx = notify_shutdown.subscribe();
+
     let (shutdown_complete_tx, shutdown_complete_rx) = mpsc::channel(1);
 
     let mut server = Listener {
         listener,
-        db_holder: DbDropGuard::new(),
-        limit_connections: Arc::new(Semaphore::new(MAX_CONNECTIONS)),
+        db_holder: DbDropGuard::new(config.persistence),
+        limit_connections: Arc::new(Semaphore::new(config.max_connections)),
         notify_shutdown,
         shutdown_complete_tx,
         shutdown_complete_rx,
+        active_connections: Arc::new(AtomicUsize::new(0)),
+        connection_added: Arc::new(Notify::new()),
+        idle_timeout: config.idle_timeout,
+        idle_shutdown: config.idle_shutdown,
     };
 
     tokio::select! {
@@ -60,18 +96,34 @@ pub async fn run(listener: TcpListener, shutdown: impl Future) {
         _ = shutdown => {
             info!("shutting down");
         }
+        _ = idle_shutdown_rx.recv() => {
+            info!("shutting down after idle timeout");
+        }
     }
 
     let Listener {
+        db_holder,
         mut shutdown_complete_rx,
         shutdown_complete_tx,
         notify_shutdown,
         ..
     } = server;
 
+    // Every `Handler` holds a clone of `notify_shutdown` (to drive the idle
+    // countdown), so dropping ours does not close the channel; send the
+    // signal explicitly so in-flight connections stop.
+    let _ = notify_shutdown.send(());
     drop(notify_shutdown);
     drop(shutdown_complete_tx);
     let _ = shutdown_complete_rx.recv().await;
+
+    // Only drop `db_holder` (and so write the final snapshot) once every
+    // in-flight `Handler` - and the `Db` clone, and commands, it's still
+    // working on - has actually finished, not merely once the accept loop
+    // has stopped.
+    drop(db_holder);
+
+    Ok(())
 }
 
 impl Listener {
@@ -84,6 +136,9 @@ impl Listener {
 
             let socket = self.accept().await?;
 
+            self.active_connections.fetch_add(1, Ordering::SeqCst);
+            self.connection_added.notify_waiters();
+
             let mut handler = Handler {
                 db: self.db_holder.db(),
 
@@ -94,6 +149,16 @@ impl Listener {
                 shutdown: Shutdown::new(self.notify_shutdown.subscribe()),
 
                 _shutdown_complete: self.shutdown_complete_tx.clone(),
+
+                active_connections: self.active_connections.clone(),
+
+                connection_added: self.connection_added.clone(),
+
+                notify_shutdown: self.notify_shutdown.clone(),
+
+                idle_timeout: self.idle_timeout,
+
+                idle_shutdown: self.idle_shutdown,
             };
 
             tokio::spawn(async move {
@@ -130,13 +195,15 @@ impl Handler {
     async fn run(&mut self) -> crate::Result<()> {
         while !self.shutdown.is_shutdown() {
             let maybe_frame = tokio::select! {
-                res = self.connection.read_frame() => res?,
+                res = read_frame(&mut self.connection, self.idle_timeout) => res?,
                 _ = self.shutdown.recv() => {
                     return Ok(());
                 }
             };
 
             let frame = match maybe_frame {
+                // `None` is also returned when the idle timeout elapses, so
+                // the connection is closed the same way as a clean EOF.
                 None => return Ok(()),
                 Some(frame) => frame,
             };
@@ -151,6 +218,22 @@ impl Handler {
     }
 }
 
+/// Reads the next frame, bounded by `idle_timeout` if one is set. Returns
+/// `Ok(None)` both on a clean EOF and on a timed-out read, so callers treat
+/// them identically.
+async fn read_frame(
+    connection: &mut Connection,
+    idle_timeout: Option<Duration>,
+) -> crate::Result<Option<Frame>> {
+    match idle_timeout {
+        Some(idle_timeout) => match time::timeout(idle_timeout, connection.read_frame()).await {
+            Ok(res) => res,
+            Err(_elapsed) => Ok(None),
+        },
+        None => connection.read_frame().await,
+    }
+}
+
 impl Drop for Handler {
     fn drop(&mut self) {
         // 将一个许可返回到Semaphore中。
@@ -159,5 +242,26 @@ impl Drop for Handler {
         // 如果add_permit方法被放在run函数的末尾，并且由于某些错误导致任务发生恐慌，那么许可将无法归还给Semaphore。
         // 通过在Drop实现中执行此操作，可以保证许可无论如何都会被归还。
         self.limit_connections.add_permits(1);
+
+        // If this was the last active connection, start a countdown; if
+        // nothing new connects before it elapses, drain the server.
+        if self.active_connections.fetch_sub(1, Ordering::SeqCst) == 1 {
+            if let Some(idle_shutdown) = self.idle_shutdown {
+                let active_connections = self.active_connections.clone();
+                let connection_added = self.connection_added.clone();
+                let notify_shutdown = self.notify_shutdown.clone();
+
+                tokio::spawn(async move {
+                    tokio::select! {
+                        _ = time::sleep(idle_shutdown) => {
+                            if active_connections.load(Ordering::SeqCst) == 0 {
+                                let _ = notify_shutdown.send(());
+                            }
+                        }
+                        _ = connection_added.notified() => {}
+                    }
+                });
+            }
+        }
     }
 }
\ No newline at end of file